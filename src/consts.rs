@@ -6,6 +6,14 @@ pub const JITO_SDK_PROGRAM_ID: &str = "7pr2BUjjdZy418NzTfqnpafR3GG3BvQyDyweM1R4k
 use lazy_static::lazy_static;
 use std::env;
 
+/// Whether the bot actually sends bundles to Jito (`Live`) or only computes and
+/// logs them for backtesting (`Mock`). Controlled by `EXECUTION_MODE=mock|live`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionMode {
+    Live,
+    Mock,
+}
+
 lazy_static! {
     pub static ref RPC_URL: String = {
         env::var("RPC_URL").unwrap_or_else(|_| "https://solana-rpc.publicnode.com".to_string())
@@ -18,4 +26,34 @@ lazy_static! {
         env::var("JITO_RPC_URL")
             .unwrap_or_else(|_| "https://frankfurt.mainnet.block-engine.jito.wtf/api/v1/bundles".to_string())
     };
+    pub static ref GEYSER_GRPC_URL: String = {
+        env::var("GEYSER_GRPC_URL")
+            .unwrap_or_else(|_| "https://solana-yellowstone-grpc.publicnode.com:443".to_string())
+    };
+    pub static ref GEYSER_X_TOKEN: Option<String> = env::var("GEYSER_X_TOKEN").ok();
+    pub static ref EXECUTION_MODE: ExecutionMode = {
+        match env::var("EXECUTION_MODE").as_deref() {
+            Ok("mock") => ExecutionMode::Mock,
+            _ => ExecutionMode::Live,
+        }
+    };
+    // Comma-separated mint list the pathfinder searches for profitable cycles
+    // over. Defaults to the original WSOL<->USDC pair.
+    pub static ref CANDIDATE_MINTS: Vec<String> = {
+        env::var("CANDIDATE_MINTS")
+            .ok()
+            .map(|v| {
+                v.split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            })
+            .unwrap_or_else(|| vec![WSOL_MINT.to_string(), USDC_MINT.to_string()])
+    };
+    pub static ref PROBE_AMOUNT_LAMPORTS: u64 = {
+        env::var("PROBE_AMOUNT_LAMPORTS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(10_000_000)
+    };
 }
@@ -0,0 +1,120 @@
+use crate::types::InstructionData;
+use anyhow::Result;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use std::env;
+use std::str::FromStr;
+
+/// Percentile (0-100) of recent prioritization fees to target when pricing
+/// compute units. Configurable via `PRIORITY_FEE_PERCENTILE` (default 75th).
+fn fee_percentile() -> u64 {
+    env::var("PRIORITY_FEE_PERCENTILE")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(75)
+}
+
+/// Fraction of `diff_lamports` allotted to the Jito tip, in basis points.
+/// Configurable via `TIP_FRACTION_BPS` (default 5000 = 50%, matching the
+/// previous `diff_lamports / 2` behavior).
+fn tip_fraction_bps() -> u64 {
+    env::var("TIP_FRACTION_BPS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(5000)
+}
+
+/// Hard ceiling on the Jito tip, in lamports. Configurable via
+/// `FEE_BUDGET_CAP_LAMPORTS` (default: unbounded, i.e. only `diff_lamports`
+/// itself constrains the tip).
+fn fee_budget_cap_lamports() -> u64 {
+    env::var("FEE_BUDGET_CAP_LAMPORTS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(u64::MAX)
+}
+
+/// A compute-unit price and Jito tip derived from live write-lock contention.
+#[derive(Debug, Clone, Copy)]
+pub struct FeePlan {
+    pub compute_unit_price_micro_lamports: u64,
+    pub jito_tip: u64,
+}
+
+/// Collects the write-locked account pubkeys referenced by a set of route
+/// instructions (the `is_writable` metas Jupiter returns alongside each
+/// instruction).
+pub fn writable_accounts(instructions: &[&InstructionData]) -> Vec<Pubkey> {
+    instructions
+        .iter()
+        .flat_map(|ix| ix.accounts.iter())
+        .filter(|acc| acc.is_writable)
+        .filter_map(|acc| Pubkey::from_str(&acc.pubkey).ok())
+        .collect()
+}
+
+/// Sizes the Jito tip off `diff_lamports` alone: `min(diff_lamports *
+/// tip_fraction, fee_budget_cap)`. Needs no RPC round trip, so callers can use
+/// it to size a quote's slippage threshold before the route's instructions
+/// (and therefore its write-locked accounts) are known.
+pub fn plan_tip(diff_lamports: u64) -> u64 {
+    std::cmp::min(
+        diff_lamports.saturating_mul(tip_fraction_bps()) / 10_000,
+        fee_budget_cap_lamports(),
+    )
+}
+
+/// Derives a compute-unit price from the configured percentile of recent
+/// prioritization fees paid on `writable`, and sizes the Jito tip via
+/// [`plan_tip`]. Returns `Ok(None)` when the resulting priority fee (using
+/// the route's actual `compute_unit_limit`) plus tip would not stay strictly
+/// below `diff_lamports`, in which case the caller should skip the trade.
+pub fn plan_fees(
+    client: &RpcClient,
+    writable: &[Pubkey],
+    diff_lamports: u64,
+    compute_unit_limit: u64,
+) -> Result<Option<FeePlan>> {
+    let mut observed_fees: Vec<u64> = client
+        .get_recent_prioritization_fees(writable)?
+        .into_iter()
+        .map(|f| f.prioritization_fee)
+        .collect();
+    observed_fees.sort_unstable();
+
+    let compute_unit_price_micro_lamports = percentile(&observed_fees, fee_percentile());
+    let jito_tip = plan_tip(diff_lamports);
+
+    let priority_fee = priority_fee_lamports(compute_unit_price_micro_lamports, compute_unit_limit);
+
+    let total_spend = priority_fee.saturating_add(jito_tip);
+    if total_spend >= diff_lamports {
+        log::info!(
+            "fee plan exceeds diffLamports ({} >= {}), skipping trade",
+            total_spend,
+            diff_lamports
+        );
+        return Ok(None);
+    }
+
+    Ok(Some(FeePlan {
+        compute_unit_price_micro_lamports,
+        jito_tip,
+    }))
+}
+
+/// Converts a compute-unit price into the lamport priority fee it charges
+/// over `compute_unit_limit` units. Shared by [`plan_fees`] and callers that
+/// need to re-derive the priority fee for an already-computed [`FeePlan`]
+/// (e.g. when sizing a retry's escalated tip).
+pub fn priority_fee_lamports(compute_unit_price_micro_lamports: u64, compute_unit_limit: u64) -> u64 {
+    compute_unit_price_micro_lamports.saturating_mul(compute_unit_limit) / 1_000_000
+}
+
+fn percentile(sorted: &[u64], pct: u64) -> u64 {
+    if sorted.is_empty() {
+        return 1;
+    }
+    let idx = (sorted.len() - 1) * pct as usize / 100;
+    sorted[idx]
+}
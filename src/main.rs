@@ -4,11 +4,21 @@ use env_logger::Builder;
 use log::LevelFilter;
 use std::io::Write;
 use std::time::Duration;
+use stream::GeyserSource;
+use tokio::sync::mpsc;
 
 mod bot;
 mod consts;
+mod fees;
+mod pathfinder;
+mod stream;
 mod types;
 
+/// Upper bound on how long to wait for a Geyser account-change notification
+/// before re-running anyway, so a down/misconfigured stream degrades back to
+/// polling instead of wedging the bot permanently.
+const RUN_FALLBACK_INTERVAL: Duration = Duration::from_secs(5);
+
 #[tokio::main]
 async fn main() -> Result<()> {
     // Initialize logger with timestamp
@@ -29,10 +39,50 @@ async fn main() -> Result<()> {
 
     let bot = ArbitrageBot::new()?;
 
+    let (tx, mut rx) = mpsc::channel(32);
+    let mut watch_handle: Option<tokio::task::JoinHandle<()>> = None;
+    let mut current_watched: Vec<String> = Vec::new();
+
     loop {
         if let Err(e) = bot.run().await {
             log::error!("Error running bot: {}", e);
         }
-        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        // (Re)subscribe only when the watched set actually changed: tearing
+        // down and reconnecting the Geyser stream on every iteration would
+        // drop updates during the reconnect gap and reset `watch()`'s own
+        // backoff, even though the pool set is almost always unchanged.
+        let mut watched = bot.watched_accounts();
+        watched.sort_unstable();
+        if !watched.is_empty() && watched != current_watched {
+            if let Some(handle) = watch_handle.take() {
+                handle.abort();
+            }
+            let source = GeyserSource::new(watched.clone());
+            let change_tx = tx.clone();
+            watch_handle = Some(tokio::spawn(async move {
+                source.watch(change_tx).await;
+            }));
+            current_watched = watched;
+        }
+
+        match &watch_handle {
+            Some(_) => {
+                // Wait for a watched pool/vault account to change, but don't
+                // wedge forever if the Geyser endpoint is down or misconfigured
+                // and never delivers one.
+                if tokio::time::timeout(RUN_FALLBACK_INTERVAL, rx.recv())
+                    .await
+                    .is_err()
+                {
+                    log::debug!("no account change within fallback interval, re-running anyway");
+                }
+            }
+            None => {
+                // No route discovered yet (e.g. first iteration); fall back to a
+                // short sleep until we have accounts worth watching.
+                tokio::time::sleep(Duration::from_millis(200)).await;
+            }
+        }
     }
 }
@@ -0,0 +1,111 @@
+/// Maximum number of hops in a candidate cycle, keeping the merged transaction
+/// within Solana's account-count limits.
+pub const MAX_CYCLE_HOPS: usize = 4;
+
+/// Directed graph over a fixed set of mints. Edge `(i, j)` carries
+/// `-ln(out_amount / in_amount)` for a probe-sized quote swapping `mints[i]`
+/// into `mints[j]`; a negative-weight cycle in this graph corresponds to a
+/// net-profitable loop after fees.
+pub struct MintGraph {
+    pub mints: Vec<String>,
+    edges: Vec<Vec<Option<f64>>>,
+}
+
+impl MintGraph {
+    pub fn new(mints: Vec<String>) -> Self {
+        let n = mints.len();
+        Self {
+            mints,
+            edges: vec![vec![None; n]; n],
+        }
+    }
+
+    pub fn set_edge(&mut self, from: usize, to: usize, in_amount: u64, out_amount: u64) {
+        if in_amount == 0 || out_amount == 0 {
+            return;
+        }
+        self.edges[from][to] = Some(-((out_amount as f64) / (in_amount as f64)).ln());
+    }
+
+    /// Runs Bellman-Ford from `source` and returns the first negative-weight
+    /// cycle it detects, rotated to start and end at `source` (the wallet only
+    /// holds `source`-denominated funds, so a cycle rooted elsewhere can't be
+    /// executed). Returns `None` if that cycle does not pass through `source`;
+    /// other cycles are not searched. Capped at `max_hops` edges to keep the
+    /// merged transaction within account limits.
+    pub fn find_negative_cycle(&self, source: usize, max_hops: usize) -> Option<Vec<usize>> {
+        let n = self.mints.len();
+        if n == 0 {
+            return None;
+        }
+
+        let mut dist = vec![f64::INFINITY; n];
+        let mut pred: Vec<Option<usize>> = vec![None; n];
+        dist[source] = 0.0;
+
+        // Standard Bellman-Ford needs n-1 rounds to guarantee shortest paths
+        // have settled; only then is a still-relaxable vertex a valid
+        // certificate of a negative-weight cycle.
+        let rounds = n.saturating_sub(1);
+        for _ in 0..rounds {
+            for u in 0..n {
+                if dist[u].is_infinite() {
+                    continue;
+                }
+                for v in 0..n {
+                    if let Some(w) = self.edges[u][v] {
+                        if dist[u] + w < dist[v] {
+                            dist[v] = dist[u] + w;
+                            pred[v] = Some(u);
+                        }
+                    }
+                }
+            }
+        }
+
+        // One extra pass: any vertex still relaxable sits on (or downstream of)
+        // a negative-weight cycle.
+        let mut cycle_node = None;
+        'outer: for u in 0..n {
+            if dist[u].is_infinite() {
+                continue;
+            }
+            for v in 0..n {
+                if let Some(w) = self.edges[u][v] {
+                    if dist[u] + w < dist[v] {
+                        cycle_node = Some(v);
+                        break 'outer;
+                    }
+                }
+            }
+        }
+
+        let mut node = cycle_node?;
+        // Walk back n steps to guarantee landing strictly inside the cycle.
+        for _ in 0..n {
+            node = pred[node]?;
+        }
+
+        let mut cycle = vec![node];
+        let mut cur = pred[node]?;
+        while cur != node {
+            cycle.push(cur);
+            if cycle.len() > max_hops {
+                // Longer than our hop cap; not a usable cycle.
+                return None;
+            }
+            cur = pred[cur]?;
+        }
+        cycle.push(node);
+        cycle.reverse();
+
+        // Reject (rather than silently execute) any cycle that doesn't pass
+        // through the base mint, and rotate it so it begins and ends there.
+        let root_pos = cycle[..cycle.len() - 1]
+            .iter()
+            .position(|&mint| mint == source)?;
+        let mut rooted = cycle[root_pos..cycle.len() - 1].to_vec();
+        rooted.extend_from_slice(&cycle[..=root_pos]);
+        Some(rooted)
+    }
+}
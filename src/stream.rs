@@ -0,0 +1,97 @@
+use crate::consts::{GEYSER_GRPC_URL, GEYSER_X_TOKEN};
+use anyhow::{Context, Result};
+use futures::StreamExt;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use yellowstone_grpc_client::GeyserGrpcClient;
+use yellowstone_grpc_proto::geyser::{
+    subscribe_update::UpdateOneof, CommitmentLevel, SubscribeRequest,
+    SubscribeRequestFilterAccounts,
+};
+
+/// Emitted whenever one of the watched pool/vault accounts changes, carrying the
+/// slot the update landed in. Drives `ArbitrageBot::run()` in place of the old
+/// fixed-interval sleep.
+#[derive(Debug, Clone, Copy)]
+pub struct AccountChange {
+    pub slot: u64,
+}
+
+/// Geyser gRPC subscription over the writable pool/vault accounts touched by the
+/// most recent successful quote's `route_plan`.
+pub struct GeyserSource {
+    accounts: Vec<String>,
+}
+
+impl GeyserSource {
+    pub fn new(accounts: Vec<String>) -> Self {
+        Self { accounts }
+    }
+
+    /// Connects, subscribes, and forwards an [`AccountChange`] for every update.
+    /// Never returns: on any stream error it backs off and resubscribes.
+    pub async fn watch(self, tx: mpsc::Sender<AccountChange>) {
+        let mut backoff = Duration::from_secs(1);
+        loop {
+            match self.subscribe_once(&tx).await {
+                Ok(()) => {
+                    log::warn!("geyser stream closed, resubscribing in {:?}", backoff);
+                    tokio::time::sleep(backoff).await;
+                    backoff = Duration::from_secs(1);
+                }
+                Err(e) => {
+                    log::error!("geyser stream error: {}, retrying in {:?}", e, backoff);
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(Duration::from_secs(30));
+                }
+            }
+        }
+    }
+
+    async fn subscribe_once(&self, tx: &mpsc::Sender<AccountChange>) -> Result<()> {
+        let mut client = GeyserGrpcClient::build_from_shared(GEYSER_GRPC_URL.to_string())?
+            .x_token(GEYSER_X_TOKEN.clone())?
+            .connect()
+            .await
+            .context("failed to connect to geyser endpoint")?;
+
+        let mut accounts_filter = HashMap::new();
+        accounts_filter.insert(
+            "watched_pools".to_string(),
+            SubscribeRequestFilterAccounts {
+                account: self.accounts.clone(),
+                owner: vec![],
+                filters: vec![],
+                nonempty_txn_signature: None,
+            },
+        );
+
+        let request = SubscribeRequest {
+            accounts: accounts_filter,
+            commitment: Some(CommitmentLevel::Processed as i32),
+            ..Default::default()
+        };
+
+        let (mut subscribe_tx, mut stream) = client.subscribe().await?;
+        subscribe_tx.send(request).await?;
+
+        while let Some(message) = stream.next().await {
+            let update = message.context("geyser stream yielded an error")?;
+            if let Some(UpdateOneof::Account(account_update)) = update.update_oneof {
+                if tx
+                    .send(AccountChange {
+                        slot: account_update.slot,
+                    })
+                    .await
+                    .is_err()
+                {
+                    // Receiver dropped, nothing left to drive.
+                    return Ok(());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}